@@ -16,4 +16,5 @@
 //!
 //! * `damerau_levenshtein` - Compute the Damerau-Levenshtein distance between two event logs.
 
+pub mod sequence_metrics;
 pub mod string_distances;
@@ -0,0 +1,206 @@
+//! Normalized similarity measures over event sequences.
+//!
+//! These operate on the same `Vec<String>` event sequences produced by
+//! [`concatenate_columns`](crate::string_distances), so callers can pick whichever metric suits
+//! their comparison. Every metric here returns an `f64` in `0.0..=1.0`, directly comparable with
+//! [`similarity`](crate::string_distances::similarity).
+
+/// The Hamming similarity between two event sequences: the fraction of positions at which the two
+/// sequences agree.
+///
+/// # Errors
+///
+/// Returns an error if `a` and `b` have different lengths, since Hamming distance is only defined
+/// between sequences of equal length.
+pub fn hamming_similarity(a: &Vec<String>, b: &Vec<String>) -> Result<f64, String> {
+    if a.len() != b.len() {
+        return Err(format!(
+            "hamming_similarity requires sequences of equal length, got {} and {}",
+            a.len(),
+            b.len()
+        ));
+    }
+
+    if a.is_empty() {
+        return Ok(1.0);
+    }
+
+    let mismatches = a.iter().zip(b.iter()).filter(|(x, y)| x != y).count();
+
+    Ok(1.0 - (mismatches as f64 / a.len() as f64))
+}
+
+/// The Jaro similarity between two event sequences.
+pub fn jaro_similarity(a: &Vec<String>, b: &Vec<String>) -> f64 {
+    let m = a.len();
+    let n = b.len();
+
+    if m == 0 && n == 0 {
+        return 1.0;
+    }
+    if m == 0 || n == 0 {
+        return 0.0;
+    }
+
+    let window = (std::cmp::max(m, n) / 2).saturating_sub(1);
+
+    let mut a_matches = vec![false; m];
+    let mut b_matches = vec![false; n];
+    let mut matches = 0;
+
+    for i in 0..m {
+        let lo = i.saturating_sub(window);
+        let hi = std::cmp::min(n - 1, i + window);
+
+        for j in lo..=hi {
+            if !b_matches[j] && a[i] == b[j] {
+                a_matches[i] = true;
+                b_matches[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let mut transpositions = 0;
+    let mut k = 0;
+
+    for i in 0..m {
+        if a_matches[i] {
+            while !b_matches[k] {
+                k += 1;
+            }
+            if a[i] != b[k] {
+                transpositions += 1;
+            }
+            k += 1;
+        }
+    }
+
+    let matches = matches as f64;
+
+    (matches / m as f64 + matches / n as f64 + (matches - transpositions as f64 / 2.0) / matches)
+        / 3.0
+}
+
+/// The Jaro-Winkler similarity between two event sequences: the Jaro similarity boosted by a bonus
+/// for a common prefix, which rewards sequences that agree on their earliest events.
+pub fn jaro_winkler_similarity(a: &Vec<String>, b: &Vec<String>) -> f64 {
+    const PREFIX_WEIGHT: f64 = 0.1;
+    const MAX_PREFIX_LEN: usize = 4;
+
+    let jaro = jaro_similarity(a, b);
+
+    let prefix_len = a
+        .iter()
+        .zip(b.iter())
+        .take(MAX_PREFIX_LEN)
+        .take_while(|(x, y)| x == y)
+        .count();
+
+    jaro + prefix_len as f64 * PREFIX_WEIGHT * (1.0 - jaro)
+}
+
+/// The length of the longest common subsequence of two event sequences, normalized by the length
+/// of the longer sequence.
+pub fn lcs_similarity(a: &Vec<String>, b: &Vec<String>) -> f64 {
+    let m = a.len();
+    let n = b.len();
+
+    if m == 0 && n == 0 {
+        return 1.0;
+    }
+    if m == 0 || n == 0 {
+        return 0.0;
+    }
+
+    let mut lengths = vec![vec![0; n + 1]; m + 1];
+
+    for i in 1..m + 1 {
+        for j in 1..n + 1 {
+            if a[i - 1] == b[j - 1] {
+                lengths[i][j] = lengths[i - 1][j - 1] + 1;
+            } else {
+                lengths[i][j] = std::cmp::max(lengths[i - 1][j], lengths[i][j - 1]);
+            }
+        }
+    }
+
+    lengths[m][n] as f64 / std::cmp::max(m, n) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hamming_similarity() {
+        let a = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let b = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+
+        assert_eq!(hamming_similarity(&a, &b), Ok(1.0));
+
+        let c = vec!["foo".to_string(), "bar".to_string(), "alice".to_string()];
+
+        assert!((hamming_similarity(&a, &c).unwrap() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hamming_similarity_length_mismatch() {
+        let a = vec!["foo".to_string(), "bar".to_string()];
+        let b = vec!["foo".to_string()];
+
+        assert!(hamming_similarity(&a, &b).is_err());
+    }
+
+    #[test]
+    fn test_jaro_similarity_identical() {
+        let a = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let b = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+
+        assert_eq!(jaro_similarity(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn test_jaro_similarity_transposition() {
+        let a = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let b = vec!["foo".to_string(), "baz".to_string(), "bar".to_string()];
+
+        let similarity = jaro_similarity(&a, &b);
+
+        assert!(similarity > 0.0 && similarity < 1.0);
+    }
+
+    #[test]
+    fn test_jaro_winkler_rewards_common_prefix() {
+        let a = vec![
+            "foo".to_string(),
+            "bar".to_string(),
+            "baz".to_string(),
+            "zzz".to_string(),
+        ];
+        let b = vec![
+            "foo".to_string(),
+            "bar".to_string(),
+            "baz".to_string(),
+            "qux".to_string(),
+        ];
+
+        let jaro = jaro_similarity(&a, &b);
+        let jaro_winkler = jaro_winkler_similarity(&a, &b);
+
+        assert!(jaro_winkler >= jaro);
+    }
+
+    #[test]
+    fn test_lcs_similarity() {
+        let a = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let b = vec!["foo".to_string(), "baz".to_string()];
+
+        assert_eq!(lcs_similarity(&a, &b), 2.0 / 3.0);
+    }
+}
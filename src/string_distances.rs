@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use polars::prelude::DataType::Datetime;
 use polars::prelude::TimeUnit::Milliseconds;
 use polars::prelude::*;
@@ -33,18 +35,63 @@ pub fn damerau_levenshtein_on_logs(
     let a_col = concatenate_columns(&df_a, columns);
     let b_col = concatenate_columns(&df_b, columns);
 
-    let distance = damerau_levenshtein(&a_col, &b_col);
+    let distance = damerau_levenshtein_banded(&a_col, &b_col);
 
     let similarity = similarity(distance, a_col.len());
 
     (distance, similarity)
 }
 
+/// Like [`damerau_levenshtein_on_logs`], but aborts as soon as the distance is known to exceed
+/// `max_distance`, returning `None` in that case instead of the full `(distance, similarity)` pair.
+///
+/// Useful when callers only care whether two logs are within a threshold of each other: the
+/// dynamic-programming pass only computes cells within `max_distance` of the diagonal and stops at
+/// the first row whose minimum exceeds it. The full `(m+1) x (n+1)` matrix is still allocated and
+/// initialized, so memory and set-up time remain O(m·n).
+///
+/// # Examples
+///
+/// ```no_run
+/// use similarity_metrics::string_distances::damerau_levenshtein_on_logs_bounded;
+///
+/// let result = damerau_levenshtein_on_logs_bounded("filename_one.csv", "filename_two.csv", &["concept:name", "org:resource", "start_timestamp", "time:timestamp"], 5);
+/// ```
+pub fn damerau_levenshtein_on_logs_bounded(
+    filename_one: &str,
+    filename_two: &str,
+    columns: &[&str],
+    max_distance: usize,
+) -> Option<(usize, f64)> {
+    let df_a = load_log_df(filename_one).unwrap();
+    let df_b = load_log_df(filename_two).unwrap();
+
+    let a_col = concatenate_columns(&df_a, columns);
+    let b_col = concatenate_columns(&df_b, columns);
+
+    let distance = damerau_levenshtein_bounded(&a_col, &b_col, max_distance)?;
+
+    let similarity = similarity(distance, a_col.len());
+
+    Some((distance, similarity))
+}
+
 /// The Damerau-Levenshtein distance calculation given two vectors of strings.
 pub fn damerau_levenshtein(log_one: &Vec<String>, log_two: &Vec<String>) -> usize {
+    damerau_levenshtein_generic(log_one, log_two)
+}
+
+/// The Damerau-Levenshtein distance calculation given two slices of any element type that
+/// supports equality comparison.
+///
+/// This is the core used by [`damerau_levenshtein`]. Comparing on opaque tokens (e.g. an [`Event`]
+/// built from several raw columns) rather than a single concatenated string avoids conflating
+/// field boundaries, since e.g. activity `"AB"` + resource `"C"` would otherwise collide with
+/// activity `"A"` + resource `"BC"`.
+pub fn damerau_levenshtein_generic<T: PartialEq>(a: &[T], b: &[T]) -> usize {
     // Compute the lengths of the event logs
-    let m = log_one.len();
-    let n = log_two.len();
+    let m = a.len();
+    let n = b.len();
 
     // Create a matrix to store the Damerau-Levenshtein distances
     let mut distance = vec![vec![0; n + 1]; m + 1];
@@ -60,14 +107,19 @@ pub fn damerau_levenshtein(log_one: &Vec<String>, log_two: &Vec<String>) -> usiz
     // Iterate over each row and column in the matrix
     for i in 1..m + 1 {
         for j in 1..n + 1 {
-            // If the characters in the two logs are the same, the distance is equal to the value in the previous cell
-            if log_one[i - 1] == log_two[j - 1] {
+            // If the elements in the two logs are the same, the distance is equal to the value in the previous cell
+            if a[i - 1] == b[j - 1] {
                 distance[i][j] = distance[i - 1][j - 1];
             } else {
                 // Otherwise, the distance is the minimum of the previous row, column, or diagonal plus one
                 distance[i][j] = std::cmp::min(distance[i - 1][j] + 1, distance[i][j - 1] + 1);
                 distance[i][j] = std::cmp::min(distance[i][j], distance[i - 1][j - 1] + 1);
             }
+
+            // Adjacent transposition: swapping the two preceding elements also reaches this cell
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance[i][j] = std::cmp::min(distance[i][j], distance[i - 2][j - 2] + 1);
+            }
         }
     }
 
@@ -75,12 +127,291 @@ pub fn damerau_levenshtein(log_one: &Vec<String>, log_two: &Vec<String>) -> usiz
     distance[m][n]
 }
 
+/// The Damerau-Levenshtein distance calculation given two vectors of strings, using only O(n) memory.
+///
+/// This keeps three rolling rows instead of the full `(m+1) x (n+1)` matrix that [`damerau_levenshtein`]
+/// allocates, which matters once event logs run into the tens of thousands of events. It returns the same
+/// distance and is the variant used by [`damerau_levenshtein_on_logs`].
+pub fn damerau_levenshtein_banded(log_one: &Vec<String>, log_two: &Vec<String>) -> usize {
+    let m = log_one.len();
+    let n = log_two.len();
+
+    // prev2/prev1/cur correspond to rows i-2, i-1, and i of the full matrix
+    let mut prev2: Vec<usize> = vec![0; n + 1];
+    let mut prev1: Vec<usize> = (0..n + 1).collect();
+    let mut cur: Vec<usize> = vec![0; n + 1];
+
+    for i in 1..m + 1 {
+        cur[0] = i;
+
+        for j in 1..n + 1 {
+            if log_one[i - 1] == log_two[j - 1] {
+                cur[j] = prev1[j - 1];
+            } else {
+                cur[j] = std::cmp::min(prev1[j] + 1, cur[j - 1] + 1);
+                cur[j] = std::cmp::min(cur[j], prev1[j - 1] + 1);
+            }
+
+            // Adjacent transposition: swapping the two preceding elements also reaches this cell
+            if i > 1
+                && j > 1
+                && log_one[i - 1] == log_two[j - 2]
+                && log_one[i - 2] == log_two[j - 1]
+            {
+                cur[j] = std::cmp::min(cur[j], prev2[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut prev2, &mut prev1);
+        std::mem::swap(&mut prev1, &mut cur);
+    }
+
+    prev1[n]
+}
+
+/// The Damerau-Levenshtein distance calculation given two vectors of strings, bounded by `max_distance`.
+///
+/// Only matrix cells within `max_distance` of the diagonal are filled, with cells outside that band
+/// treated as infinity; once a row's minimum already exceeds `max_distance`, the logs cannot possibly
+/// be within the threshold and `None` is returned immediately. Returns `None` whenever the true
+/// distance is greater than `max_distance`.
+pub fn damerau_levenshtein_bounded(
+    log_one: &Vec<String>,
+    log_two: &Vec<String>,
+    max_distance: usize,
+) -> Option<usize> {
+    let m = log_one.len();
+    let n = log_two.len();
+
+    if m.abs_diff(n) > max_distance {
+        return None;
+    }
+
+    // Cells outside the band are never actually infinite, just far enough that they cannot win a `min`
+    const OUT_OF_BAND: usize = usize::MAX / 4;
+
+    let mut distance = vec![vec![OUT_OF_BAND; n + 1]; m + 1];
+
+    for i in 0..=std::cmp::min(m, max_distance) {
+        distance[i][0] = i;
+    }
+    for j in 0..=std::cmp::min(n, max_distance) {
+        distance[0][j] = j;
+    }
+
+    for i in 1..m + 1 {
+        let lo = i.saturating_sub(max_distance);
+        let hi = std::cmp::min(n, i + max_distance);
+
+        // distance[i][0] was seeded above when i <= max_distance, but the band scan below starts
+        // at j = 1, so it would otherwise be missed when checking whether the row already exceeds
+        // max_distance (notably when log_two is empty).
+        let mut row_min = distance[i][0];
+
+        for j in std::cmp::max(lo, 1)..hi + 1 {
+            if log_one[i - 1] == log_two[j - 1] {
+                distance[i][j] = distance[i - 1][j - 1];
+            } else {
+                distance[i][j] = std::cmp::min(distance[i - 1][j] + 1, distance[i][j - 1] + 1);
+                distance[i][j] = std::cmp::min(distance[i][j], distance[i - 1][j - 1] + 1);
+            }
+
+            // Adjacent transposition: swapping the two preceding elements also reaches this cell
+            if i > 1
+                && j > 1
+                && log_one[i - 1] == log_two[j - 2]
+                && log_one[i - 2] == log_two[j - 1]
+            {
+                distance[i][j] = std::cmp::min(distance[i][j], distance[i - 2][j - 2] + 1);
+            }
+
+            row_min = std::cmp::min(row_min, distance[i][j]);
+        }
+
+        if row_min > max_distance {
+            return None;
+        }
+    }
+
+    let result = distance[m][n];
+
+    if result > max_distance {
+        None
+    } else {
+        Some(result)
+    }
+}
+
 /// Computes the similarity between two event logs given the Damerau-Levenshtein distance and the length of any
 /// of the event logs.
 pub fn similarity(distance: usize, length: usize) -> f64 {
     1.0 - (distance as f64 / length as f64)
 }
 
+/// The q-gram distance calculation given two event logs and columns to concatenate into a string.
+///
+/// Complements edit distance: it captures local ordering differences cheaply by comparing the
+/// multisets of contiguous `q`-length windows of the two logs, without a full DP pass. For `q = 1`
+/// this degenerates to a bag-of-activities difference, a useful baseline for process comparison.
+///
+/// # Returns
+///
+/// * `distance` - The q-gram distance, i.e. the sum of absolute count differences across the union
+///   of q-grams seen in either log.
+/// * `similarity` - The cosine similarity of the two logs' q-gram count vectors.
+pub fn qgram_on_logs(filename_one: &str, filename_two: &str, columns: &[&str], q: usize) -> (i64, f64) {
+    let df_a = load_log_df(filename_one).unwrap();
+    let df_b = load_log_df(filename_two).unwrap();
+
+    let a_col = concatenate_columns(&df_a, columns);
+    let b_col = concatenate_columns(&df_b, columns);
+
+    let distance = qgram_distance(&a_col, &b_col, q);
+    let similarity = qgram_similarity(&a_col, &b_col, q);
+
+    (distance, similarity)
+}
+
+/// The q-gram distance between two event logs: the sum of absolute count differences across the
+/// union of contiguous `q`-length windows seen in either log.
+pub fn qgram_distance(log_one: &Vec<String>, log_two: &Vec<String>, q: usize) -> i64 {
+    let counts_one = qgram_counts(log_one, q);
+    let counts_two = qgram_counts(log_two, q);
+
+    let qgrams: HashSet<&Vec<String>> = counts_one.keys().chain(counts_two.keys()).collect();
+
+    qgrams
+        .iter()
+        .map(|qgram| {
+            let a = *counts_one.get(*qgram).unwrap_or(&0);
+            let b = *counts_two.get(*qgram).unwrap_or(&0);
+            (a - b).abs()
+        })
+        .sum()
+}
+
+/// The cosine similarity between two event logs' q-gram count vectors, in `0.0..=1.0`.
+pub fn qgram_similarity(log_one: &Vec<String>, log_two: &Vec<String>, q: usize) -> f64 {
+    let counts_one = qgram_counts(log_one, q);
+    let counts_two = qgram_counts(log_two, q);
+
+    let qgrams: HashSet<&Vec<String>> = counts_one.keys().chain(counts_two.keys()).collect();
+
+    let mut dot_product = 0i64;
+    let mut norm_one = 0i64;
+    let mut norm_two = 0i64;
+
+    for qgram in qgrams {
+        let a = *counts_one.get(qgram).unwrap_or(&0);
+        let b = *counts_two.get(qgram).unwrap_or(&0);
+
+        dot_product += a * b;
+        norm_one += a * a;
+        norm_two += b * b;
+    }
+
+    if norm_one == 0 || norm_two == 0 {
+        return if norm_one == norm_two { 1.0 } else { 0.0 };
+    }
+
+    dot_product as f64 / ((norm_one as f64).sqrt() * (norm_two as f64).sqrt())
+}
+
+fn qgram_counts(log: &[String], q: usize) -> HashMap<Vec<String>, i64> {
+    let mut counts = HashMap::new();
+
+    if q == 0 || log.len() < q {
+        return counts;
+    }
+
+    for window in log.windows(q) {
+        *counts.entry(window.to_vec()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// The cost of each edit operation for [`weighted_edit_distance`]. Defaults to 1 for every
+/// operation, matching plain edit distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Weights {
+    pub insert: usize,
+    pub delete: usize,
+    pub substitute: usize,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+        }
+    }
+}
+
+/// Like [`damerau_levenshtein_on_logs`], but lets callers assign distinct costs to insertion,
+/// deletion, and substitution (Sellers edit distance) instead of the hard-coded cost of 1 each.
+///
+/// Unlike [`damerau_levenshtein_on_logs`], adjacent transpositions are not counted as a single
+/// operation, so with default weights a swapped pair of events costs 2 here rather than 1.
+pub fn damerau_levenshtein_on_logs_weighted(
+    filename_one: &str,
+    filename_two: &str,
+    columns: &[&str],
+    weights: Weights,
+) -> (usize, f64) {
+    let df_a = load_log_df(filename_one).unwrap();
+    let df_b = load_log_df(filename_two).unwrap();
+
+    let a_col = concatenate_columns(&df_a, columns);
+    let b_col = concatenate_columns(&df_b, columns);
+
+    let distance = weighted_edit_distance(&a_col, &b_col, weights);
+
+    let similarity = similarity(distance, a_col.len());
+
+    (distance, similarity)
+}
+
+/// The Sellers edit distance between two vectors of strings: plain edit distance with
+/// configurable per-operation costs instead of a hard-coded 1 for insertion, deletion, and
+/// substitution. Lets callers penalize e.g. a missing activity more heavily than a reordered one.
+pub fn weighted_edit_distance(
+    log_one: &Vec<String>,
+    log_two: &Vec<String>,
+    weights: Weights,
+) -> usize {
+    let m = log_one.len();
+    let n = log_two.len();
+
+    let mut distance = vec![vec![0; n + 1]; m + 1];
+
+    for i in 0..m + 1 {
+        distance[i][0] = i * weights.delete;
+    }
+    for j in 0..n + 1 {
+        distance[0][j] = j * weights.insert;
+    }
+
+    for i in 1..m + 1 {
+        for j in 1..n + 1 {
+            if log_one[i - 1] == log_two[j - 1] {
+                distance[i][j] = distance[i - 1][j - 1];
+            } else {
+                distance[i][j] = std::cmp::min(
+                    distance[i - 1][j] + weights.delete,
+                    distance[i][j - 1] + weights.insert,
+                );
+                distance[i][j] =
+                    std::cmp::min(distance[i][j], distance[i - 1][j - 1] + weights.substitute);
+            }
+        }
+    }
+
+    distance[m][n]
+}
+
 fn concatenate_columns(input: &DataFrame, column_names: &[&str]) -> Vec<String> {
     assert_ne!(column_names.len(), 0, "No columns to concatenate");
     assert_ne!(column_names.len(), 1, "Only one column to concatenate");
@@ -110,6 +441,57 @@ fn concatenate_columns(input: &DataFrame, column_names: &[&str]) -> Vec<String>
         .collect()
 }
 
+/// An event built from its raw column values kept as separate fields, rather than fused into a
+/// single string. Two events are equal only when every field matches exactly, so e.g. activity
+/// `"AB"` + resource `"C"` no longer collides with activity `"A"` + resource `"BC"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    fields: Vec<String>,
+}
+
+fn events_from_columns(input: &DataFrame, column_names: &[&str]) -> Vec<Event> {
+    assert_ne!(column_names.len(), 0, "No columns to build events from");
+
+    let columns: Vec<_> = column_names
+        .iter()
+        .map(|name| input.column(name).unwrap().utf8().unwrap())
+        .collect();
+
+    (0..input.height())
+        .map(|row| Event {
+            fields: columns
+                .iter()
+                .map(|column| column.get(row).unwrap().to_string())
+                .collect(),
+        })
+        .collect()
+}
+
+/// Loads an event log and its `columns` as structured [`Event`]s instead of a concatenated string
+/// per event.
+pub fn load_log_events(filename: &str, columns: &[&str]) -> PolarsResult<Vec<Event>> {
+    let df = load_log_df(filename)?;
+
+    Ok(events_from_columns(&df, columns))
+}
+
+/// Like [`damerau_levenshtein_on_logs`], but compares events on exact field equality instead of
+/// concatenating columns into a single string.
+pub fn damerau_levenshtein_on_logs_generic(
+    filename_one: &str,
+    filename_two: &str,
+    columns: &[&str],
+) -> (usize, f64) {
+    let a_events = load_log_events(filename_one, columns).unwrap();
+    let b_events = load_log_events(filename_two, columns).unwrap();
+
+    let distance = damerau_levenshtein_generic(&a_events, &b_events);
+
+    let similarity = similarity(distance, a_events.len());
+
+    (distance, similarity)
+}
+
 fn load_log_df(filename: &str) -> PolarsResult<DataFrame> {
     // TODO: refactor hard-coded column names
 
@@ -189,4 +571,138 @@ mod tests {
 
         assert_eq!(distance, 1);
     }
+
+    #[test]
+    fn test_damerau_levenshtein_transposition() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "baz".to_string(), "bar".to_string()];
+
+        let distance = damerau_levenshtein(&log_one, &log_two);
+
+        assert_eq!(distance, 1);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_banded_matches_full_matrix() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+
+        assert_eq!(damerau_levenshtein_banded(&log_one, &log_two), 0);
+
+        let log_three = vec!["foo".to_string(), "bar".to_string(), "alice".to_string()];
+
+        assert_eq!(
+            damerau_levenshtein_banded(&log_one, &log_three),
+            damerau_levenshtein(&log_one, &log_three)
+        );
+
+        let log_four = vec!["foo".to_string(), "baz".to_string(), "bar".to_string()];
+
+        assert_eq!(
+            damerau_levenshtein_banded(&log_one, &log_four),
+            damerau_levenshtein(&log_one, &log_four)
+        );
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_bounded_within_threshold() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "bar".to_string(), "alice".to_string()];
+
+        assert_eq!(damerau_levenshtein_bounded(&log_one, &log_two, 1), Some(1));
+        assert_eq!(
+            damerau_levenshtein_bounded(&log_one, &log_two, 2),
+            damerau_levenshtein_bounded(&log_one, &log_two, 5)
+        );
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_bounded_exceeds_threshold() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["alice".to_string(), "bob".to_string(), "carol".to_string()];
+
+        assert_eq!(damerau_levenshtein_bounded(&log_one, &log_two, 2), None);
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_bounded_against_empty_log() {
+        let log_one = vec!["foo".to_string()];
+        let log_two: Vec<String> = vec![];
+
+        assert_eq!(damerau_levenshtein_bounded(&log_one, &log_two, 1), Some(1));
+        assert_eq!(damerau_levenshtein_bounded(&log_two, &log_one, 1), Some(1));
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_generic() {
+        let log_one = vec![1, 2, 3];
+        let log_two = vec![1, 3, 2];
+
+        assert_eq!(damerau_levenshtein_generic(&log_one, &log_two), 1);
+    }
+
+    #[test]
+    fn test_events_from_columns_distinguishes_field_boundaries() {
+        let df = DataFrame::new(vec![
+            Series::new("activity", &["AB", "A"]),
+            Series::new("resource", &["C", "BC"]),
+        ])
+        .unwrap();
+
+        let events = events_from_columns(&df, &["activity", "resource"]);
+
+        assert_ne!(events[0], events[1]);
+    }
+
+    #[test]
+    fn test_qgram_distance_identical_logs() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+
+        assert_eq!(qgram_distance(&log_one, &log_two, 2), 0);
+        assert!((qgram_similarity(&log_one, &log_two, 2) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_qgram_distance_bag_of_activities_for_q_one() {
+        let log_one = vec!["foo".to_string(), "bar".to_string()];
+        let log_two = vec!["bar".to_string(), "foo".to_string(), "baz".to_string()];
+
+        // q = 1 ignores order entirely, so only the extra "baz" contributes to the distance
+        assert_eq!(qgram_distance(&log_one, &log_two, 1), 1);
+    }
+
+    #[test]
+    fn test_qgram_distance_sensitive_to_order_for_q_two() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "baz".to_string(), "bar".to_string()];
+
+        assert_eq!(qgram_distance(&log_one, &log_one, 2), 0);
+        assert!(qgram_distance(&log_one, &log_two, 2) > 0);
+    }
+
+    #[test]
+    fn test_weighted_edit_distance_defaults_match_damerau_levenshtein() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "bar".to_string(), "alice".to_string()];
+
+        assert_eq!(
+            weighted_edit_distance(&log_one, &log_two, Weights::default()),
+            damerau_levenshtein(&log_one, &log_two)
+        );
+    }
+
+    #[test]
+    fn test_weighted_edit_distance_penalizes_deletion_more() {
+        let log_one = vec!["foo".to_string(), "bar".to_string(), "baz".to_string()];
+        let log_two = vec!["foo".to_string(), "bar".to_string()];
+
+        let weights = Weights {
+            insert: 1,
+            delete: 10,
+            substitute: 1,
+        };
+
+        assert_eq!(weighted_edit_distance(&log_one, &log_two, weights), 10);
+    }
 }